@@ -1,4 +1,112 @@
 use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::marker::PhantomData;
+
+pub trait MerkleHasher {
+    fn hash_leaf(data: &[u8]) -> [u8; 32];
+    fn hash_internal(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32];
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha256Hasher;
+
+impl MerkleHasher for Sha256Hasher {
+    fn hash_leaf(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(&[0x00]);
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    fn hash_internal(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(&[0x01]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// A key-value backend for `MerkleTree::persist`/`open`. Nodes are keyed by
+// their own hash, but a full `persist` call still writes every node and a
+// full `open` still loads every leaf into memory — this is snapshot
+// save/restore, not an on-demand store that can back a tree too large to
+// fit in RAM.
+pub trait TreeStore {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+    fn put(&mut self, key: Vec<u8>, value: Vec<u8>);
+    fn delete(&mut self, key: &[u8]);
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStore {
+    entries: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TreeStore for InMemoryStore {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries.get(key).cloned()
+    }
+
+    fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        self.entries.insert(key, value);
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.entries.remove(key);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    directory: std::path::PathBuf,
+}
+
+impl FileStore {
+    pub fn new(directory: impl Into<std::path::PathBuf>) -> std::io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(FileStore { directory })
+    }
+
+    fn path_for(&self, key: &[u8]) -> std::path::PathBuf {
+        self.directory.join(to_hex(key))
+    }
+}
+
+impl TreeStore for FileStore {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        std::fs::read(self.path_for(key)).ok()
+    }
+
+    fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        let path = self.path_for(&key);
+        std::fs::write(&path, value)
+            .unwrap_or_else(|err| panic!("Failed to write node to {}: {}", path.display(), err));
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        let path = self.path_for(key);
+        if let Err(err) = std::fs::remove_file(&path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                panic!("Failed to delete node at {}: {}", path.display(), err);
+            }
+        }
+    }
+}
+
+const ROOT_STORE_KEY: &[u8] = b"rusty-merkle-tree/root";
+const DEPTH_STORE_KEY: &[u8] = b"rusty-merkle-tree/fixed-depth";
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Side {
@@ -7,31 +115,98 @@ pub enum Side {
 }
 
 #[derive(Debug, Clone)]
-pub struct MerkleTree {
+struct FixedDepthState {
+    depth: usize,
+    zero_hashes: Vec<[u8; 32]>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MerkleTree<H: MerkleHasher = Sha256Hasher> {
     root: [u8; 32],
-    leaves: Vec<[u8; 32]>,
+    leaf_count: usize,
+    nodes: Vec<[u8; 32]>,
+    // Dynamic (non-fixed-depth) mode stores each level in its own Vec so that
+    // `add_leaf_hash` only has to touch the O(log n) nodes on the path to the
+    // root, instead of rebuilding one flat, concatenated-levels array (whose
+    // per-level offsets all shift on every append) from scratch.
+    levels: Vec<Vec<[u8; 32]>>,
+    fixed_depth: Option<FixedDepthState>,
+    _hasher: PhantomData<H>,
 }
 
 #[derive(Debug, Clone)]
-pub struct MerkleProof {
+pub struct MerkleProof<H: MerkleHasher = Sha256Hasher> {
     pairs: Vec<([u8; 32], Side)>,
+    _hasher: PhantomData<H>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MerkleMultiProof<H: MerkleHasher = Sha256Hasher> {
+    leaf_count: usize,
+    indices: Vec<usize>,
+    sibling_hashes: Vec<[u8; 32]>,
+    _hasher: PhantomData<H>,
 }
 
-impl MerkleTree {
+impl MerkleTree<Sha256Hasher> {
     pub fn new(array: Vec<&str>) -> Result<Self, String> {
+        Self::with_hasher(array)
+    }
+
+    pub fn with_depth(depth: usize) -> Result<Self, String> {
+        Self::with_depth_and_hasher(depth)
+    }
+
+    pub fn open<S: TreeStore>(store: &S) -> Result<Self, String> {
+        Self::open_with_hasher(store)
+    }
+}
+
+impl<H: MerkleHasher> MerkleTree<H> {
+    pub fn with_hasher(array: Vec<&str>) -> Result<Self, String> {
         if array.is_empty() {
             return Err("You can't create an empty Merkle Tree".to_string());
         }
 
-        let mut leaves = Vec::with_capacity(array.len());
+        let mut tree = MerkleTree {
+            root: [0u8; 32],
+            leaf_count: 0,
+            nodes: Vec::new(),
+            levels: Vec::new(),
+            fixed_depth: None,
+            _hasher: PhantomData,
+        };
+
         for element in array {
-            let leaf = Self::hash_leaf(element.as_bytes());
-            leaves.push(leaf);
+            tree.add_leaf(element);
         }
 
-        let root = Self::calculate_merkle_root(&leaves);
+        Ok(tree)
+    }
+
+    pub fn with_depth_and_hasher(depth: usize) -> Result<Self, String> {
+        if depth > 63 {
+            return Err(
+                "A fixed-depth Merkle Tree can't have more than 63 levels".to_string()
+            );
+        }
 
-        Ok(MerkleTree { root, leaves })
+        let mut zero_hashes = Vec::with_capacity(depth + 1);
+        zero_hashes.push(Self::hash_leaf(&[]));
+        for level in 0..depth {
+            let child = zero_hashes[level];
+            zero_hashes.push(Self::hash_internal(&child, &child));
+        }
+        let root = zero_hashes[depth];
+
+        Ok(MerkleTree {
+            root,
+            leaf_count: 0,
+            nodes: Vec::new(),
+            levels: Vec::new(),
+            fixed_depth: Some(FixedDepthState { depth, zero_hashes }),
+            _hasher: PhantomData,
+        })
     }
 
     pub fn root(&self) -> [u8; 32] {
@@ -39,79 +214,428 @@ impl MerkleTree {
     }
 
     pub fn leaves_count(&self) -> usize {
-        self.leaves.len()
+        self.leaf_count
     }
 
     pub fn leaf_at(&self, idx: usize) -> [u8; 32] {
-        self.leaves[idx]
+        if self.fixed_depth.is_some() {
+            self.nodes[idx]
+        } else {
+            self.levels[0][idx]
+        }
+    }
+
+    // Writes a full snapshot of the tree: every leaf and interior node is
+    // rewritten on every call, so cost scales with the whole tree rather
+    // than with what changed since the last persist.
+    pub fn persist<S: TreeStore>(&self, store: &mut S) {
+        store.put(ROOT_STORE_KEY.to_vec(), self.root.to_vec());
+
+        if let Some(state) = &self.fixed_depth {
+            store.put(DEPTH_STORE_KEY.to_vec(), state.depth.to_le_bytes().to_vec());
+            Self::persist_fixed_depth_subtree(state.depth, &self.nodes, &state.zero_hashes, store);
+            return;
+        }
+
+        for &leaf in &self.levels[0] {
+            store.put(leaf.to_vec(), vec![0]);
+        }
+
+        for level in 1..self.levels.len() {
+            let child_level = &self.levels[level - 1];
+
+            for (i, &parent) in self.levels[level].iter().enumerate() {
+                let left_idx = 2 * i;
+
+                // A lone leftover node is promoted unchanged, so its hash is
+                // identical to its child's and already has a record in the
+                // store — writing it again would make the node its own child.
+                if left_idx + 1 >= child_level.len() {
+                    continue;
+                }
+
+                let mut value = vec![1u8];
+                value.extend_from_slice(&child_level[left_idx]);
+                value.extend_from_slice(&child_level[left_idx + 1]);
+                store.put(parent.to_vec(), value);
+            }
+        }
+    }
+
+    pub fn load_root<S: TreeStore>(store: &S) -> Option<[u8; 32]> {
+        store.get(ROOT_STORE_KEY)?.try_into().ok()
+    }
+
+    // Rebuilds the tree by walking every node reachable from the persisted
+    // root and loading every leaf into memory before replaying them through
+    // `add_leaf_hash`. This restores a full snapshot; it does not page
+    // nodes in lazily, so the whole leaf set must fit in memory at once.
+    pub fn open_with_hasher<S: TreeStore>(store: &S) -> Result<Self, String> {
+        let root = Self::load_root(store)
+            .ok_or_else(|| "No persisted root was found in the given TreeStore".to_string())?;
+
+        if let Some(depth_bytes) = store.get(DEPTH_STORE_KEY) {
+            let depth_bytes: [u8; 8] = depth_bytes
+                .try_into()
+                .map_err(|_| "Corrupt fixed-depth marker in the given TreeStore".to_string())?;
+            let depth = usize::from_le_bytes(depth_bytes);
+
+            let mut tree = Self::with_depth_and_hasher(depth)?;
+            let zero_hashes = tree.fixed_depth.as_ref().unwrap().zero_hashes.clone();
+            let leaf_hashes =
+                Self::collect_fixed_depth_leaf_hashes(store, root, depth, &zero_hashes)?;
+
+            for leaf_hash in leaf_hashes {
+                tree.add_leaf_hash(leaf_hash);
+            }
+
+            if tree.root != root {
+                return Err(
+                    "The persisted root does not match its reconstructed leaves".to_string()
+                );
+            }
+
+            return Ok(tree);
+        }
+
+        let leaf_hashes = Self::collect_leaf_hashes(store, root)?;
+        if leaf_hashes.is_empty() {
+            return Err("You can't create an empty Merkle Tree".to_string());
+        }
+
+        let mut tree = MerkleTree {
+            root: [0u8; 32],
+            leaf_count: 0,
+            nodes: Vec::new(),
+            levels: Vec::new(),
+            fixed_depth: None,
+            _hasher: PhantomData,
+        };
+
+        for leaf_hash in leaf_hashes {
+            tree.add_leaf_hash(leaf_hash);
+        }
+
+        if tree.root != root {
+            return Err("The persisted root does not match its reconstructed leaves".to_string());
+        }
+
+        Ok(tree)
+    }
+
+    fn collect_leaf_hashes<S: TreeStore>(
+        store: &S,
+        node: [u8; 32],
+    ) -> Result<Vec<[u8; 32]>, String> {
+        let value = store
+            .get(&node)
+            .ok_or_else(|| "Missing node in the given TreeStore".to_string())?;
+
+        match value.first() {
+            Some(0) => Ok(vec![node]),
+            Some(1) if value.len() == 65 => {
+                let mut left = [0u8; 32];
+                left.copy_from_slice(&value[1..33]);
+                let mut right = [0u8; 32];
+                right.copy_from_slice(&value[33..65]);
+
+                let mut leaves = Self::collect_leaf_hashes(store, left)?;
+                leaves.extend(Self::collect_leaf_hashes(store, right)?);
+                Ok(leaves)
+            }
+            _ => Err("Corrupt node encoding in the given TreeStore".to_string()),
+        }
+    }
+
+    fn persist_fixed_depth_subtree<S: TreeStore>(
+        level: usize,
+        leaves: &[[u8; 32]],
+        zero_hashes: &[[u8; 32]],
+        store: &mut S,
+    ) -> [u8; 32] {
+        if leaves.is_empty() {
+            return zero_hashes[level];
+        }
+        if level == 0 {
+            let hash = leaves[0];
+            store.put(hash.to_vec(), vec![0]);
+            return hash;
+        }
+
+        let half = 1usize << (level - 1);
+        let (left, right) = if leaves.len() <= half {
+            (leaves, &[][..])
+        } else {
+            leaves.split_at(half)
+        };
+
+        let left_hash = Self::persist_fixed_depth_subtree(level - 1, left, zero_hashes, store);
+        let right_hash = Self::persist_fixed_depth_subtree(level - 1, right, zero_hashes, store);
+        let hash = Self::hash_internal(&left_hash, &right_hash);
+
+        let mut value = vec![1u8];
+        value.extend_from_slice(&left_hash);
+        value.extend_from_slice(&right_hash);
+        store.put(hash.to_vec(), value);
+
+        hash
+    }
+
+    fn collect_fixed_depth_leaf_hashes<S: TreeStore>(
+        store: &S,
+        node: [u8; 32],
+        level: usize,
+        zero_hashes: &[[u8; 32]],
+    ) -> Result<Vec<[u8; 32]>, String> {
+        if node == zero_hashes[level] {
+            return Ok(Vec::new());
+        }
+        if level == 0 {
+            return Ok(vec![node]);
+        }
+
+        let value = store
+            .get(&node)
+            .ok_or_else(|| "Missing node in the given TreeStore".to_string())?;
+
+        match value.first() {
+            Some(1) if value.len() == 65 => {
+                let mut left = [0u8; 32];
+                left.copy_from_slice(&value[1..33]);
+                let mut right = [0u8; 32];
+                right.copy_from_slice(&value[33..65]);
+
+                let mut leaves =
+                    Self::collect_fixed_depth_leaf_hashes(store, left, level - 1, zero_hashes)?;
+                leaves.extend(Self::collect_fixed_depth_leaf_hashes(
+                    store,
+                    right,
+                    level - 1,
+                    zero_hashes,
+                )?);
+                Ok(leaves)
+            }
+            _ => Err("Corrupt node encoding in the given TreeStore".to_string()),
+        }
     }
 
     pub fn add_leaf(&mut self, element: &str) {
-        let new_hash = Self::hash_leaf(element.as_bytes());
-        self.leaves.push(new_hash);
-        self.root = Self::calculate_merkle_root(&self.leaves);
+        let leaf_hash = Self::hash_leaf(element.as_bytes());
+        self.add_leaf_hash(leaf_hash);
     }
 
-    pub fn formulate_proof_of_inclusion(&self, data: &str) -> Option<MerkleProof> {
+    fn add_leaf_hash(&mut self, leaf_hash: [u8; 32]) {
+        if let Some(state) = self.fixed_depth.clone() {
+            assert!(
+                self.leaf_count < (1usize << state.depth),
+                "fixed-depth Merkle Tree of depth {} is already full",
+                state.depth
+            );
+
+            self.nodes.push(leaf_hash);
+            self.leaf_count += 1;
+
+            // `self.levels` caches every non-empty node hash of each level
+            // (the rightmost one still growing) so appending only walks the O(log n) path to the
+            // root, instead of re-deriving the whole subtree from `self.nodes`
+            // via `fixed_depth_subtree` on every call.
+            if self.levels.is_empty() {
+                self.levels.push(Vec::new());
+            }
+            self.levels[0].push(leaf_hash);
+
+            let mut node_hash = leaf_hash;
+            for level in 0..state.depth {
+                let index_in_level = self.levels[level].len() - 1;
+
+                let parent_hash = if index_in_level % 2 == 1 {
+                    let left = self.levels[level][index_in_level - 1];
+                    Self::hash_internal(&left, &node_hash)
+                } else {
+                    Self::hash_internal(&node_hash, &state.zero_hashes[level])
+                };
+
+                if level + 1 >= self.levels.len() {
+                    self.levels.push(Vec::new());
+                }
+                let parent_index = index_in_level / 2;
+                if parent_index < self.levels[level + 1].len() {
+                    self.levels[level + 1][parent_index] = parent_hash;
+                } else {
+                    self.levels[level + 1].push(parent_hash);
+                }
+
+                node_hash = parent_hash;
+            }
+
+            self.root = node_hash;
+            return;
+        }
+
+        if self.levels.is_empty() {
+            self.levels.push(Vec::new());
+        }
+        self.levels[0].push(leaf_hash);
+        self.leaf_count += 1;
+
+        let mut level = 0;
+        let mut node_hash = leaf_hash;
+
+        loop {
+            let index_in_level = self.levels[level].len() - 1;
+
+            if level + 1 >= self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+
+            let parent_hash = if index_in_level % 2 == 1 {
+                let left = self.levels[level][index_in_level - 1];
+                Self::hash_internal(&left, &node_hash)
+            } else {
+                // A lone trailing node with no sibling yet is promoted unchanged.
+                node_hash
+            };
+
+            let parent_index = index_in_level / 2;
+            if parent_index < self.levels[level + 1].len() {
+                self.levels[level + 1][parent_index] = parent_hash;
+            } else {
+                self.levels[level + 1].push(parent_hash);
+            }
+
+            node_hash = parent_hash;
+            level += 1;
+
+            if self.levels[level].len() == 1 {
+                break;
+            }
+        }
+
+        self.root = node_hash;
+    }
+
+    pub fn formulate_proof_of_inclusion(&self, data: &str) -> Option<MerkleProof<H>> {
         let leaf_hash = Self::hash_leaf(data.as_bytes());
-        let index = self.leaves.iter().position(|h| h == &leaf_hash)?;
+
+        if let Some(state) = &self.fixed_depth {
+            let index = (0..self.leaf_count).find(|&i| self.nodes[i] == leaf_hash)?;
+            return Some(Self::fixed_depth_proof(index, &self.nodes, state));
+        }
+
+        let mut index = (0..self.leaf_count).find(|&i| self.levels[0][i] == leaf_hash)?;
 
         let mut elements_of_proof = Vec::new();
-        Self::formulate_proof_recursive(&self.leaves, index, &mut elements_of_proof);
+        for level in 0..self.levels.len() - 1 {
+            let len = self.levels[level].len();
+
+            if index % 2 == 0 {
+                if index + 1 < len {
+                    elements_of_proof.push((self.levels[level][index + 1], Side::Right));
+                }
+            } else {
+                elements_of_proof.push((self.levels[level][index - 1], Side::Left));
+            }
+
+            index /= 2;
+        }
 
         Some(MerkleProof {
             pairs: elements_of_proof,
+            _hasher: PhantomData,
         })
     }
 
-    fn formulate_proof_recursive(
-        current_level: &[[u8; 32]],
-        index: usize,
-        elements_of_proof: &mut Vec<([u8; 32], Side)>,
-    ) {
-        if current_level.len() <= 1 {
-            return;
+    pub fn formulate_multiproof(&self, data: &[&str]) -> Option<MerkleMultiProof<H>> {
+        let mut ordered_indices = Vec::with_capacity(data.len());
+        for datum in data {
+            let leaf_hash = Self::hash_leaf(datum.as_bytes());
+            let index = (0..self.leaf_count).find(|&i| self.levels[0][i] == leaf_hash)?;
+            ordered_indices.push(index);
         }
 
-        let is_even_index = index % 2 == 0;
-        if is_even_index {
-            if index + 1 < current_level.len() {
-                elements_of_proof.push((current_level[index + 1], Side::Right));
+        // `verify` pairs `self.indices` positionally with the caller's `leaves`
+        // slice, so the `indices` field must stay in the order `data`
+        // was given rather than the ascending order used below to walk the tree.
+        let mut sorted_indices = ordered_indices.clone();
+        sorted_indices.sort_unstable();
+        sorted_indices.dedup();
+
+        let mut sibling_hashes = Vec::new();
+        let mut current_indices = sorted_indices;
+
+        for level in 0..self.levels.len() - 1 {
+            let len = self.levels[level].len();
+            let index_set: HashSet<usize> = current_indices.iter().copied().collect();
+            let mut next_indices = Vec::with_capacity(current_indices.len());
+
+            for &index in &current_indices {
+                let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+                if sibling_index < len && !index_set.contains(&sibling_index) {
+                    sibling_hashes.push(self.levels[level][sibling_index]);
+                }
+                next_indices.push(index / 2);
             }
-        } else {
-            elements_of_proof.push((current_level[index - 1], Side::Left));
+            next_indices.dedup();
+
+            current_indices = next_indices;
         }
 
-        Self::formulate_proof_recursive(
-            &Self::calculate_next_level_of_tree(current_level),
-            index / 2,
-            elements_of_proof,
-        );
+        Some(MerkleMultiProof {
+            leaf_count: self.leaf_count,
+            indices: ordered_indices,
+            sibling_hashes,
+            _hasher: PhantomData,
+        })
     }
 
-    fn calculate_merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
-        match leaves {
-            [] => unreachable!("Empty leaves in calculate_merkle_root"),
-            [single] => *single,
-            _ => {
-                let next_level_of_tree = Self::calculate_next_level_of_tree(leaves);
-                Self::calculate_merkle_root(&next_level_of_tree)
-            }
+    fn fixed_depth_subtree(
+        level: usize,
+        leaves: &[[u8; 32]],
+        zero_hashes: &[[u8; 32]],
+    ) -> [u8; 32] {
+        if leaves.is_empty() {
+            return zero_hashes[level];
+        }
+        if level == 0 {
+            return leaves[0];
         }
-    }
 
-    fn calculate_next_level_of_tree(leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
-        let mut next_level_of_tree = Vec::with_capacity((leaves.len() + 1) / 2);
+        let half = 1usize << (level - 1);
+        let (left, right) = if leaves.len() <= half {
+            (leaves, &[][..])
+        } else {
+            leaves.split_at(half)
+        };
 
-        for pair in leaves.chunks(2) {
-            if pair.len() == 2 {
-                next_level_of_tree.push(Self::hash_internal(&pair[0], &pair[1]));
-            } else {
-                next_level_of_tree.push(pair[0]);
-            }
+        Self::hash_internal(
+            &Self::fixed_depth_subtree(level - 1, left, zero_hashes),
+            &Self::fixed_depth_subtree(level - 1, right, zero_hashes),
+        )
+    }
+
+    fn fixed_depth_proof(
+        mut index: usize,
+        nodes: &[[u8; 32]],
+        state: &FixedDepthState,
+    ) -> MerkleProof<H> {
+        let mut pairs = Vec::with_capacity(state.depth);
+
+        for level in 0..state.depth {
+            let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+            let start = (sibling_index << level).min(nodes.len());
+            let end = ((sibling_index + 1) << level).min(nodes.len());
+            let sibling_leaves = &nodes[start..end];
+            let sibling_hash = Self::fixed_depth_subtree(level, sibling_leaves, &state.zero_hashes);
+
+            let side = if index % 2 == 0 { Side::Right } else { Side::Left };
+            pairs.push((sibling_hash, side));
+            index /= 2;
         }
 
-        next_level_of_tree
+        MerkleProof {
+            pairs,
+            _hasher: PhantomData,
+        }
     }
 
     pub fn hash_bytes(data: &[u8]) -> [u8; 32] {
@@ -121,33 +645,26 @@ impl MerkleTree {
     }
 
     pub fn hash_leaf(data: &[u8]) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(&[0x00]);
-        hasher.update(data);
-        hasher.finalize().into()
+        H::hash_leaf(data)
     }
 
     pub fn hash_internal(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(&[0x01]);
-        hasher.update(left);
-        hasher.update(right);
-        hasher.finalize().into()
+        H::hash_internal(left, right)
     }
 
     pub fn root_hex(&self) -> String {
-        self.root.iter().map(|b| format!("{:02x}", b)).collect()
+        to_hex(&self.root)
     }
 }
 
-impl MerkleProof {
+impl<H: MerkleHasher> MerkleProof<H> {
     pub fn verify(&self, root: [u8; 32], leaf: &str) -> bool {
-        let mut current_hash = MerkleTree::hash_leaf(leaf.as_bytes());
+        let mut current_hash = H::hash_leaf(leaf.as_bytes());
 
         for (sibling_hash, side) in &self.pairs {
             current_hash = match side {
-                Side::Left => MerkleTree::hash_internal(sibling_hash, &current_hash),
-                Side::Right => MerkleTree::hash_internal(&current_hash, sibling_hash),
+                Side::Left => H::hash_internal(sibling_hash, &current_hash),
+                Side::Right => H::hash_internal(&current_hash, sibling_hash),
             };
         }
 
@@ -155,16 +672,286 @@ impl MerkleProof {
     }
 }
 
+impl<H: MerkleHasher> MerkleMultiProof<H> {
+    pub fn verify(&self, root: [u8; 32], leaves: &[&str]) -> bool {
+        if leaves.len() != self.indices.len() {
+            return false;
+        }
+
+        let mut current: BTreeMap<usize, [u8; 32]> = self
+            .indices
+            .iter()
+            .copied()
+            .zip(leaves.iter().map(|leaf| H::hash_leaf(leaf.as_bytes())))
+            .collect();
+
+        let mut sibling_hashes = self.sibling_hashes.iter();
+        let mut level_len = self.leaf_count;
+
+        while level_len > 1 {
+            let mut next = BTreeMap::new();
+
+            for (&index, &hash) in current.iter() {
+                let parent = index / 2;
+                if next.contains_key(&parent) {
+                    continue;
+                }
+
+                let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+                let node_hash = if sibling_index >= level_len {
+                    hash
+                } else {
+                    let sibling_hash = match current.get(&sibling_index) {
+                        Some(&known) => known,
+                        None => match sibling_hashes.next() {
+                            Some(&supplied) => supplied,
+                            None => return false,
+                        },
+                    };
+
+                    if index % 2 == 0 {
+                        H::hash_internal(&hash, &sibling_hash)
+                    } else {
+                        H::hash_internal(&sibling_hash, &hash)
+                    }
+                };
+
+                next.insert(parent, node_hash);
+            }
+
+            current = next;
+            level_len = (level_len + 1) / 2;
+        }
+
+        sibling_hashes.next().is_none() && current.get(&0) == Some(&root)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseMerkleTree {
+    num_levels: usize,
+    root: [u8; 32],
+    empty_hashes: Vec<[u8; 32]>,
+    nodes: HashMap<(usize, u64), [u8; 32]>,
+    keys: HashMap<u64, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseProof {
+    path: u64,
+    num_levels: usize,
+    leaf_hash: [u8; 32],
+    siblings: Vec<[u8; 32]>,
+}
+
+impl SparseMerkleTree {
+    pub fn new(num_levels: usize) -> Result<Self, String> {
+        if num_levels == 0 {
+            return Err("A Sparse Merkle Tree needs at least one level".to_string());
+        }
+        if num_levels > 64 {
+            return Err("A Sparse Merkle Tree can't have more than 64 levels".to_string());
+        }
+
+        let mut empty_hashes = Vec::with_capacity(num_levels + 1);
+        empty_hashes.push(Self::empty_leaf_sentinel());
+        for level in 0..num_levels {
+            let empty_child = empty_hashes[level];
+            empty_hashes.push(Sha256Hasher::hash_internal(&empty_child, &empty_child));
+        }
+        let root = empty_hashes[num_levels];
+
+        Ok(SparseMerkleTree {
+            num_levels,
+            root,
+            empty_hashes,
+            nodes: HashMap::new(),
+            keys: HashMap::new(),
+        })
+    }
+
+    pub fn get_root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    pub fn insert(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let path = Self::path_for_key(key, self.num_levels);
+
+        if let Some(existing_key) = self.keys.get(&path) {
+            if existing_key != key {
+                return Err(format!(
+                    "Key \"{}\" collides with already-inserted key \"{}\" at path {} — refusing to overwrite its leaf",
+                    key, existing_key, path
+                ));
+            }
+        }
+        self.keys.insert(path, key.to_string());
+
+        let leaf_hash = Sha256Hasher::hash_leaf(value.as_bytes());
+        self.nodes.insert((0, path), leaf_hash);
+
+        let mut index = path;
+        let mut current_hash = leaf_hash;
+        for level in 0..self.num_levels {
+            let sibling_index = index ^ 1;
+            let sibling_hash = self
+                .nodes
+                .get(&(level, sibling_index))
+                .copied()
+                .unwrap_or(self.empty_hashes[level]);
+
+            current_hash = if index % 2 == 0 {
+                Sha256Hasher::hash_internal(&current_hash, &sibling_hash)
+            } else {
+                Sha256Hasher::hash_internal(&sibling_hash, &current_hash)
+            };
+
+            index /= 2;
+            self.nodes.insert((level + 1, index), current_hash);
+        }
+
+        self.root = current_hash;
+
+        Ok(())
+    }
+
+    pub fn prove(&self, key: &str) -> SparseProof {
+        let path = Self::path_for_key(key, self.num_levels);
+        let leaf_hash = self
+            .nodes
+            .get(&(0, path))
+            .copied()
+            .unwrap_or(self.empty_hashes[0]);
+
+        let mut siblings = Vec::with_capacity(self.num_levels);
+        let mut index = path;
+        for level in 0..self.num_levels {
+            let sibling_index = index ^ 1;
+            let sibling_hash = self
+                .nodes
+                .get(&(level, sibling_index))
+                .copied()
+                .unwrap_or(self.empty_hashes[level]);
+            siblings.push(sibling_hash);
+            index /= 2;
+        }
+
+        SparseProof {
+            path,
+            num_levels: self.num_levels,
+            leaf_hash,
+            siblings,
+        }
+    }
+
+    // Domain-separated from `Sha256Hasher::hash_leaf` (tag 0x00) and
+    // `hash_internal` (tag 0x01), so an empty slot's hash can never equal a
+    // real leaf's hash and be mistaken for an inserted value — in
+    // particular, it must not collide with `hash_leaf("")`.
+    fn empty_leaf_sentinel() -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update([0x02]);
+        hasher.finalize().into()
+    }
+
+    fn path_for_key(key: &str, num_levels: usize) -> u64 {
+        let hash = Sha256Hasher::hash_leaf(key.as_bytes());
+        let mut top_bytes = [0u8; 8];
+        top_bytes.copy_from_slice(&hash[0..8]);
+        let full = u64::from_be_bytes(top_bytes);
+
+        if num_levels >= 64 {
+            full
+        } else {
+            full >> (64 - num_levels)
+        }
+    }
+}
+
+impl SparseProof {
+    fn recomputed_root(&self) -> [u8; 32] {
+        let mut current = self.leaf_hash;
+        for (level, sibling) in self.siblings.iter().enumerate() {
+            let index = self.path >> level;
+            current = if index % 2 == 0 {
+                Sha256Hasher::hash_internal(&current, sibling)
+            } else {
+                Sha256Hasher::hash_internal(sibling, &current)
+            };
+        }
+        current
+    }
+
+    pub fn verify_inclusion(&self, root: [u8; 32], key: &str, value: &str) -> bool {
+        if self.path != SparseMerkleTree::path_for_key(key, self.num_levels) {
+            return false;
+        }
+
+        self.leaf_hash == Sha256Hasher::hash_leaf(value.as_bytes()) && self.recomputed_root() == root
+    }
+
+    pub fn verify_exclusion(&self, root: [u8; 32], key: &str, value: &str) -> bool {
+        if self.path != SparseMerkleTree::path_for_key(key, self.num_levels) {
+            return false;
+        }
+
+        self.leaf_hash != Sha256Hasher::hash_leaf(value.as_bytes()) && self.recomputed_root() == root
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn hex_to_bytes(hex: &str) -> [u8; 32] {
-        let mut bytes = [0u8; 32];
-        for i in 0..32 {
-            bytes[i] = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
-        }
-        bytes
+    fn hex_to_bytes(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for i in 0..32 {
+            bytes[i] = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    struct ReverseDigestHasher;
+
+    impl MerkleHasher for ReverseDigestHasher {
+        fn hash_leaf(data: &[u8]) -> [u8; 32] {
+            let reversed: Vec<u8> = data.iter().rev().copied().collect();
+            Sha256Hasher::hash_leaf(&reversed)
+        }
+
+        fn hash_internal(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+            Sha256Hasher::hash_internal(right, left)
+        }
+    }
+
+    #[test]
+    fn test_generic_tree_with_a_custom_hasher_diverges_from_the_default_sha256_root() {
+        let data = vec!["hola", "mundo", "lambda", "class"];
+
+        let default_tree = MerkleTree::new(data.clone()).unwrap();
+        let custom_tree = MerkleTree::<ReverseDigestHasher>::with_hasher(data).unwrap();
+
+        assert_ne!(
+            default_tree.root(),
+            custom_tree.root(),
+            "A tree built with a different MerkleHasher must not share the default Sha256 root"
+        );
+    }
+
+    #[test]
+    fn test_generic_tree_proof_verifies_under_its_own_hasher() {
+        let data = vec!["hola", "mundo", "lambda", "class"];
+        let tree = MerkleTree::<ReverseDigestHasher>::with_hasher(data).unwrap();
+        let root = tree.root();
+
+        let proof = tree
+            .formulate_proof_of_inclusion("lambda")
+            .expect("Expected a valid proof under the custom hasher");
+
+        assert!(
+            proof.verify(root, "lambda"),
+            "A proof generated under a custom MerkleHasher must verify under that same hasher"
+        );
     }
 
     #[test]
@@ -449,6 +1236,271 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_incrementally_built_tree_matches_a_tree_built_from_the_full_array() {
+        let data = vec![
+            "rust", "haskell", "c++", "python", "smalltalk", "java", "assembly", "javascript",
+            "go", "lua", "lisp",
+        ];
+
+        let batch_tree = MerkleTree::new(data.clone()).unwrap();
+
+        let mut incremental_tree = MerkleTree::new(vec![data[0]]).unwrap();
+        for element in &data[1..] {
+            incremental_tree.add_leaf(element);
+        }
+
+        assert_eq!(
+            batch_tree.root(),
+            incremental_tree.root(),
+            "Building a tree leaf-by-leaf must produce the same root as building it from the full array"
+        );
+    }
+
+    #[test]
+    fn test_multiproof_verifies_a_subset_of_leaves() {
+        let data = vec!["hola", "mundo", "lambda", "class", "rust"];
+        let tree = MerkleTree::new(data).unwrap();
+        let root = tree.root();
+        let proven_leaves = vec!["mundo", "class", "rust"];
+
+        let multiproof = tree
+            .formulate_multiproof(&proven_leaves)
+            .expect("Expected a valid multiproof to be generated for existing leaves");
+
+        assert!(
+            multiproof.verify(root, &proven_leaves),
+            "Verification failed for a valid multiproof over multiple leaves"
+        );
+    }
+
+    #[test]
+    fn test_multiproof_verifies_leaves_out_of_ascending_index_order() {
+        let data = vec!["hola", "mundo", "lambda", "class", "rust"];
+        let tree = MerkleTree::new(data).unwrap();
+        let root = tree.root();
+
+        // "rust" is at index 4 and "mundo" is at index 1, so this is the
+        // reverse of ascending tree-index order.
+        let proven_leaves = vec!["rust", "mundo"];
+
+        let multiproof = tree
+            .formulate_multiproof(&proven_leaves)
+            .expect("Expected a valid multiproof to be generated for existing leaves");
+
+        assert!(
+            multiproof.verify(root, &proven_leaves),
+            "Verification must succeed when leaves are passed in the same order they were proven, \
+             regardless of their ascending tree-index order"
+        );
+    }
+
+    #[test]
+    fn test_multiproof_is_more_compact_than_individual_proofs() {
+        let data = vec![
+            "rust", "haskell", "c++", "python", "smalltalk", "java", "assembly", "javascript",
+        ];
+        let tree = MerkleTree::new(data).unwrap();
+        let proven_leaves = vec!["rust", "haskell", "c++"];
+
+        let multiproof = tree.formulate_multiproof(&proven_leaves).unwrap();
+        let individual_sibling_count: usize = proven_leaves
+            .iter()
+            .map(|leaf| tree.formulate_proof_of_inclusion(leaf).unwrap().pairs.len())
+            .sum();
+
+        assert!(
+            multiproof.sibling_hashes.len() < individual_sibling_count,
+            "Multiproof should share sibling hashes instead of duplicating a proof per leaf"
+        );
+    }
+
+    #[test]
+    fn test_multiproof_for_non_existent_leaf_fails() {
+        let tree = MerkleTree::new(vec!["hola", "mundo", "lambda", "class"]).unwrap();
+
+        let multiproof = tree.formulate_multiproof(&["mundo", "rust"]);
+
+        assert!(
+            multiproof.is_none(),
+            "A multiproof should not be generated (must be None) when one leaf is not in the tree"
+        );
+    }
+
+    #[test]
+    fn test_multiproof_with_wrong_leaves_fails() {
+        let data = vec!["hola", "mundo", "lambda", "class", "rust"];
+        let tree = MerkleTree::new(data).unwrap();
+        let root = tree.root();
+        let proven_leaves = vec!["mundo", "class"];
+
+        let multiproof = tree.formulate_multiproof(&proven_leaves).unwrap();
+
+        assert!(
+            !multiproof.verify(root, &["mundo", "rust"]),
+            "Multiproof verification must fail when a supplied leaf doesn't match the proven set"
+        );
+    }
+
+    #[test]
+    fn test_multiproof_over_all_leaves_matches_root() {
+        let data = vec!["A", "B", "C", "D", "E", "F", "G"];
+        let tree = MerkleTree::new(data.clone()).unwrap();
+        let root = tree.root();
+
+        let multiproof = tree
+            .formulate_multiproof(&data)
+            .expect("Expected a valid multiproof for the full leaf set");
+
+        assert!(
+            multiproof.verify(root, &data),
+            "A multiproof covering every leaf should still verify against the root"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_proves_inclusion_of_an_inserted_key() {
+        let mut tree = SparseMerkleTree::new(32).unwrap();
+        tree.insert("alice", "100").unwrap();
+        tree.insert("bob", "50").unwrap();
+
+        let proof = tree.prove("alice");
+
+        assert!(
+            proof.verify_inclusion(tree.get_root(), "alice", "100"),
+            "Inclusion proof should verify for a key that was inserted with this value"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_proves_non_inclusion_of_an_absent_key() {
+        let mut tree = SparseMerkleTree::new(32).unwrap();
+        tree.insert("alice", "100").unwrap();
+
+        let proof = tree.prove("mallory");
+
+        assert!(
+            proof.verify_exclusion(tree.get_root(), "mallory", "anything"),
+            "Exclusion proof should verify for a key that was never inserted"
+        );
+        assert!(
+            !proof.verify_inclusion(tree.get_root(), "mallory", "anything"),
+            "Inclusion proof must not verify for a key that was never inserted"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_proves_exclusion_of_a_different_value_at_a_colliding_path() {
+        let num_levels = 8;
+        let key_a = "alice";
+        let path_a = SparseMerkleTree::path_for_key(key_a, num_levels);
+
+        let colliding_key = (0..100_000)
+            .map(|i| format!("key-{}", i))
+            .find(|candidate| {
+                candidate != key_a
+                    && SparseMerkleTree::path_for_key(candidate, num_levels) == path_a
+            })
+            .expect("Expected to find a colliding path within a small 8-level tree");
+
+        let mut tree = SparseMerkleTree::new(num_levels).unwrap();
+        tree.insert(key_a, "100").unwrap();
+
+        let proof = tree.prove(&colliding_key);
+
+        assert!(
+            proof.verify_exclusion(tree.get_root(), &colliding_key, "anything"),
+            "Exclusion proof should verify when the path is occupied by a different key's leaf"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_empty_string_value_does_not_forge_inclusion_of_an_absent_key() {
+        let mut tree = SparseMerkleTree::new(32).unwrap();
+        tree.insert("alice", "100").unwrap();
+
+        let proof = tree.prove("mallory");
+
+        assert!(
+            !proof.verify_inclusion(tree.get_root(), "mallory", ""),
+            "An absent key's empty slot must not be mistaken for an inserted empty-string value"
+        );
+        assert!(
+            proof.verify_exclusion(tree.get_root(), "mallory", ""),
+            "Exclusion proof should still verify for an absent key when checked against the empty string"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_insert_rejects_a_colliding_key_without_overwriting() {
+        let num_levels = 8;
+        let key_a = "alice";
+        let path_a = SparseMerkleTree::path_for_key(key_a, num_levels);
+
+        let colliding_key = (0..100_000)
+            .map(|i| format!("key-{}", i))
+            .find(|candidate| {
+                candidate != key_a
+                    && SparseMerkleTree::path_for_key(candidate, num_levels) == path_a
+            })
+            .expect("Expected to find a colliding path within a small 8-level tree");
+
+        let mut tree = SparseMerkleTree::new(num_levels).unwrap();
+        tree.insert(key_a, "100").unwrap();
+        let root_after_first_insert = tree.get_root();
+
+        let result = tree.insert(&colliding_key, "anything");
+
+        assert!(
+            result.is_err(),
+            "Inserting a key that collides with an already-inserted different key must fail"
+        );
+        assert_eq!(
+            tree.get_root(),
+            root_after_first_insert,
+            "A rejected colliding insert must not overwrite the original key's leaf"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_insert_allows_updating_the_same_key() {
+        let mut tree = SparseMerkleTree::new(32).unwrap();
+        tree.insert("alice", "100").unwrap();
+        tree.insert("alice", "200").unwrap();
+
+        let proof = tree.prove("alice");
+
+        assert!(
+            proof.verify_inclusion(tree.get_root(), "alice", "200"),
+            "Re-inserting the same key should update its value instead of being treated as a collision"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_rejects_tampered_root() {
+        let mut tree = SparseMerkleTree::new(32).unwrap();
+        tree.insert("alice", "100").unwrap();
+
+        let proof = tree.prove("alice");
+        let mut tampered_root = tree.get_root();
+        tampered_root[0] ^= 0xFF;
+
+        assert!(
+            !proof.verify_inclusion(tampered_root, "alice", "100"),
+            "Inclusion proof must not verify against a tampered root"
+        );
+    }
+
+    #[test]
+    fn test_sparse_tree_rejects_zero_levels() {
+        let tree_result = SparseMerkleTree::new(0);
+
+        assert!(
+            tree_result.is_err(),
+            "SparseMerkleTree should return an Error when created with zero levels"
+        );
+    }
+
     #[test]
     fn test_collision_vulnerability_duplication() {
         let tree_3 = MerkleTree::new(vec!["A", "B", "C"]).unwrap();
@@ -460,4 +1512,227 @@ mod tests {
             "Vulnerability found: Tree [A,B,C] and [A,B,C,C] produce the same root!"
         );
     }
+
+    #[test]
+    fn test_fixed_depth_empty_tree_root_matches_top_zero_hash() {
+        let tree = MerkleTree::with_depth(3).unwrap();
+
+        let mut expected = Sha256Hasher::hash_leaf(&[]);
+        for _ in 0..3 {
+            expected = Sha256Hasher::hash_internal(&expected, &expected);
+        }
+
+        assert_eq!(
+            tree.root(),
+            expected,
+            "An empty fixed-depth tree's root must equal the top-level zero subtree hash"
+        );
+    }
+
+    #[test]
+    fn test_fixed_depth_root_changes_as_leaves_are_appended() {
+        let mut tree = MerkleTree::with_depth(4).unwrap();
+        let empty_root = tree.root();
+
+        tree.add_leaf("hola");
+        let first_root = tree.root();
+        assert_ne!(
+            empty_root, first_root,
+            "Appending a leaf must change the root of a fixed-depth tree"
+        );
+
+        tree.add_leaf("mundo");
+        let second_root = tree.root();
+        assert_ne!(
+            first_root, second_root,
+            "Appending a second leaf must change the root again"
+        );
+    }
+
+    #[test]
+    fn test_fixed_depth_proof_verifies_before_the_tree_is_full() {
+        let mut tree = MerkleTree::with_depth(4).unwrap();
+        tree.add_leaf("hola");
+        tree.add_leaf("mundo");
+        tree.add_leaf("lambda");
+
+        let root = tree.root();
+        let proof = tree
+            .formulate_proof_of_inclusion("mundo")
+            .expect("Expected a valid proof for a leaf in a partially-filled fixed-depth tree");
+
+        assert!(
+            proof.verify(root, "mundo"),
+            "A proof from a partially-filled fixed-depth tree must verify against its root"
+        );
+        assert!(
+            !proof.verify(root, "nope"),
+            "A proof must not verify for data that was never inserted"
+        );
+    }
+
+    #[test]
+    fn test_fixed_depth_proof_length_is_always_the_configured_depth() {
+        let mut tree = MerkleTree::with_depth(5).unwrap();
+        tree.add_leaf("hola");
+
+        let proof = tree
+            .formulate_proof_of_inclusion("hola")
+            .expect("Expected a valid proof for the only inserted leaf");
+
+        assert_eq!(
+            proof.pairs.len(),
+            5,
+            "A fixed-depth proof must always contain exactly `depth` sibling hashes"
+        );
+    }
+
+    #[test]
+    fn test_fixed_depth_tree_matches_dynamic_tree_once_full() {
+        let data = vec!["A", "B", "C", "D"];
+
+        let mut fixed_tree = MerkleTree::with_depth(2).unwrap();
+        for element in &data {
+            fixed_tree.add_leaf(element);
+        }
+
+        let dynamic_tree = MerkleTree::new(data).unwrap();
+
+        assert_eq!(
+            fixed_tree.root(),
+            dynamic_tree.root(),
+            "A full fixed-depth tree should produce the same root as the equivalent dynamic tree"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "already full")]
+    fn test_fixed_depth_tree_panics_when_overfilled() {
+        let mut tree = MerkleTree::with_depth(1).unwrap();
+        tree.add_leaf("A");
+        tree.add_leaf("B");
+        tree.add_leaf("C");
+    }
+
+    #[test]
+    fn test_with_depth_rejects_a_depth_that_would_overflow_the_leaf_count_shift() {
+        let result = MerkleTree::with_depth(64);
+
+        assert!(
+            result.is_err(),
+            "A fixed-depth tree deeper than the tree can address should be rejected instead of panicking"
+        );
+    }
+
+    #[test]
+    fn test_with_depth_accepts_the_maximum_supported_depth() {
+        let result = MerkleTree::with_depth(63);
+
+        assert!(
+            result.is_ok(),
+            "The maximum addressable depth of 63 should be accepted"
+        );
+    }
+
+    #[test]
+    fn test_tree_reopened_from_an_in_memory_store_matches_the_original_root() {
+        let tree = MerkleTree::new(vec!["hola", "mundo", "lambda", "class", "fn"]).unwrap();
+
+        let mut store = InMemoryStore::new();
+        tree.persist(&mut store);
+
+        let reopened = MerkleTree::open(&store).expect("Expected to reopen a persisted tree");
+
+        assert_eq!(
+            tree.root(),
+            reopened.root(),
+            "A tree reopened from its store must have the same root as the original"
+        );
+        assert_eq!(tree.leaves_count(), reopened.leaves_count());
+    }
+
+    #[test]
+    fn test_fixed_depth_tree_reopened_from_a_store_matches_the_original_root() {
+        let mut tree = MerkleTree::with_depth(4).unwrap();
+        tree.add_leaf("hola");
+        tree.add_leaf("mundo");
+        tree.add_leaf("lambda");
+
+        let mut store = InMemoryStore::new();
+        tree.persist(&mut store);
+
+        let reopened = MerkleTree::open(&store)
+            .expect("Expected to reopen a persisted fixed-depth tree");
+
+        assert_eq!(
+            tree.root(),
+            reopened.root(),
+            "A reopened fixed-depth tree must have the same root as the original"
+        );
+        assert_eq!(tree.leaves_count(), reopened.leaves_count());
+
+        let proof = reopened
+            .formulate_proof_of_inclusion("mundo")
+            .expect("Expected a valid proof from the reopened fixed-depth tree");
+        assert!(proof.verify(tree.root(), "mundo"));
+    }
+
+    #[test]
+    fn test_proof_generated_from_a_reopened_tree_still_verifies() {
+        let tree = MerkleTree::new(vec!["hola", "mundo", "lambda"]).unwrap();
+
+        let mut store = InMemoryStore::new();
+        tree.persist(&mut store);
+
+        let reopened = MerkleTree::open(&store).unwrap();
+        let proof = reopened
+            .formulate_proof_of_inclusion("mundo")
+            .expect("Expected a valid proof from the reopened tree");
+
+        assert!(
+            proof.verify(tree.root(), "mundo"),
+            "A proof formulated from a reopened tree must verify against the original root"
+        );
+    }
+
+    #[test]
+    fn test_load_root_reads_the_persisted_root_without_rebuilding_the_tree() {
+        let tree = MerkleTree::new(vec!["hola", "mundo"]).unwrap();
+
+        let mut store = InMemoryStore::new();
+        tree.persist(&mut store);
+
+        let loaded_root = MerkleTree::<Sha256Hasher>::load_root(&store);
+
+        assert_eq!(loaded_root, Some(tree.root()));
+    }
+
+    #[test]
+    fn test_open_fails_when_the_store_has_no_persisted_root() {
+        let store = InMemoryStore::new();
+
+        let result = MerkleTree::<Sha256Hasher>::open(&store);
+
+        assert!(
+            result.is_err(),
+            "Opening a tree from an empty store should fail instead of panicking"
+        );
+    }
+
+    #[test]
+    fn test_tree_reopened_from_a_file_store_survives_across_instances() {
+        let directory = std::env::temp_dir().join("rusty_merkle_tree_test_file_store");
+        let mut store = FileStore::new(&directory).expect("Expected to create the store directory");
+
+        let tree = MerkleTree::new(vec!["hola", "mundo", "lambda"]).unwrap();
+        tree.persist(&mut store);
+
+        let reopened_store = FileStore::new(&directory).unwrap();
+        let reopened = MerkleTree::open(&reopened_store)
+            .expect("Expected to reopen a tree from a fresh FileStore instance");
+
+        assert_eq!(tree.root(), reopened.root());
+
+        std::fs::remove_dir_all(&directory).ok();
+    }
 }